@@ -0,0 +1,295 @@
+// Attribute-based access control (ABAC) over parameters and context.
+//
+// An `AccessPolicy` is a boolean expression tree of equality leaves combined
+// with `And`/`Or` (positive literals only, no negation). Leaves resolve a
+// dotted JSON path into the request — `parameters.*`, `context.*`, `tool`,
+// `action` — and compare it against an expected value. `evaluate()` runs the
+// expression and denies with a reason naming the failing leaf.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{PolicyInput, PolicyResult};
+
+/// A boolean expression tree for attribute-based access control.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum AccessPolicy {
+    /// A leaf comparing the value at a dotted path to an expected value.
+    Attr(String, Value),
+    And(Box<AccessPolicy>, Box<AccessPolicy>),
+    Or(Box<AccessPolicy>, Box<AccessPolicy>),
+}
+
+impl AccessPolicy {
+    /// Evaluate the tree against the request. `Ok(())` means the expression
+    /// held; `Err(path)` names the leaf responsible for the failure.
+    fn check(&self, input: &PolicyInput) -> Result<(), String> {
+        match self {
+            AccessPolicy::Attr(path, expected) => {
+                match resolve(input, path) {
+                    Some(ref actual) if values_equal(actual, expected) => Ok(()),
+                    _ => Err(path.clone()),
+                }
+            }
+            AccessPolicy::And(a, b) => a.check(input).and_then(|_| b.check(input)),
+            AccessPolicy::Or(a, b) => match a.check(input) {
+                Ok(()) => Ok(()),
+                // When both sides fail, surface the right-hand leaf.
+                Err(_) => b.check(input),
+            },
+        }
+    }
+
+    /// Evaluate the policy, returning `allow` when it holds and `deny` (naming
+    /// the failing leaf) otherwise.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        match self.check(input) {
+            Ok(()) => PolicyResult::allow("ABAC policy satisfied"),
+            Err(path) => PolicyResult::deny(format!("ABAC policy failed at `{path}`")),
+        }
+    }
+}
+
+/// Compare two JSON values for equality, treating numbers by their `f64` value
+/// so an integer parameter such as `1000` matches the `1000` literal the parser
+/// stores as a float `Number`.
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => match (x.as_f64(), y.as_f64()) {
+            (Some(x), Some(y)) => x == y,
+            _ => a == b,
+        },
+        _ => a == b,
+    }
+}
+
+/// Resolve a dotted path against the request's attributes.
+fn resolve(input: &PolicyInput, path: &str) -> Option<Value> {
+    let mut parts = path.split('.');
+    let head = parts.next()?;
+    let mut current = match head {
+        "parameters" => input.parameters.clone(),
+        "context" => input.context.clone(),
+        "tool" => return Some(Value::String(input.tool.clone())),
+        "action" => return Some(Value::String(input.action.clone())),
+        _ => return None,
+    };
+    for part in parts {
+        current = current.get(part)?.clone();
+    }
+    Some(current)
+}
+
+/// Parse an infix expression such as
+/// `context.role == "admin" && parameters.tier == "gold"` into an
+/// `AccessPolicy`. Precedence is `||` (lowest) then `&&`; leaves compare with
+/// `==` only. Relational operators (`<`, `>`, numeric ranges) are intentionally
+/// out of scope here — use the condition-operator engine for those.
+pub fn parse(src: &str) -> Result<AccessPolicy, String> {
+    let tokens = tokenize(src)?;
+    let mut parser = ExprParser { tokens: &tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("unexpected token at {}", parser.pos));
+    }
+    Ok(expr)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    Eq,
+    And,
+    Or,
+    LParen,
+    RParen,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Tok>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ws if ws.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Tok::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Tok::RParen);
+                i += 1;
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Tok::And);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Tok::Or);
+                i += 2;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Tok::Eq);
+                i += 2;
+            }
+            '"' | '\'' => {
+                let quote = c;
+                i += 1;
+                let mut s = String::new();
+                while i < chars.len() && chars[i] != quote {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err("unterminated string literal".into());
+                }
+                i += 1;
+                tokens.push(Tok::Str(s));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut s = String::new();
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '-') {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                let n: f64 = s.parse().map_err(|_| format!("invalid number: {s}"))?;
+                tokens.push(Tok::Num(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut s = String::new();
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                match s.as_str() {
+                    "true" => tokens.push(Tok::Bool(true)),
+                    "false" => tokens.push(Tok::Bool(false)),
+                    _ => tokens.push(Tok::Ident(s)),
+                }
+            }
+            '<' | '>' => {
+                return Err(format!(
+                    "relational operator `{c}` is not supported in ABAC expressions; \
+                     only `==` equality is available (use the condition-operator engine \
+                     for numeric ranges)"
+                ))
+            }
+            other => return Err(format!("unexpected character: {other}")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct ExprParser<'a> {
+    tokens: &'a [Tok],
+    pos: usize,
+}
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<AccessPolicy, String> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Tok::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = AccessPolicy::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<AccessPolicy, String> {
+        let mut left = self.parse_primary()?;
+        while self.peek() == Some(&Tok::And) {
+            self.pos += 1;
+            let right = self.parse_primary()?;
+            left = AccessPolicy::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_primary(&mut self) -> Result<AccessPolicy, String> {
+        if self.peek() == Some(&Tok::LParen) {
+            self.pos += 1;
+            let expr = self.parse_or()?;
+            if self.tokens.get(self.pos) != Some(&Tok::RParen) {
+                return Err("expected closing parenthesis".into());
+            }
+            self.pos += 1;
+            return Ok(expr);
+        }
+        // A leaf: path == value
+        let path = match self.tokens.get(self.pos).cloned() {
+            Some(Tok::Ident(p)) => p,
+            other => return Err(format!("expected attribute path, found {other:?}")),
+        };
+        self.pos += 1;
+        if self.tokens.get(self.pos) != Some(&Tok::Eq) {
+            return Err(format!("expected `==` after `{path}`"));
+        }
+        self.pos += 1;
+        let value = match self.tokens.get(self.pos).cloned() {
+            Some(Tok::Str(s)) => Value::String(s),
+            Some(Tok::Num(n)) => serde_json::json!(n),
+            Some(Tok::Bool(b)) => Value::Bool(b),
+            other => return Err(format!("expected literal value, found {other:?}")),
+        };
+        self.pos += 1;
+        Ok(AccessPolicy::Attr(path, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input() -> PolicyInput {
+        PolicyInput {
+            tool: "db".into(),
+            action: "read".into(),
+            parameters: json!({"tier": "gold"}),
+            context: json!({"role": "admin"}),
+        }
+    }
+
+    #[test]
+    fn test_parse_and_satisfied() {
+        let p = parse(r#"context.role == "admin" && parameters.tier == "gold""#).unwrap();
+        assert!(p.evaluate(&input()).allowed);
+    }
+
+    #[test]
+    fn test_failing_leaf_named() {
+        let p = parse(r#"context.role == "admin" && parameters.tier == "silver""#).unwrap();
+        let r = p.evaluate(&input());
+        assert!(!r.allowed);
+        assert!(r.reason.contains("parameters.tier"));
+    }
+
+    #[test]
+    fn test_numeric_equality_matches_integer_param() {
+        let mut inp = input();
+        inp.parameters = json!({"amount": 1000});
+        let p = parse("parameters.amount == 1000").unwrap();
+        assert!(p.evaluate(&inp).allowed);
+    }
+
+    #[test]
+    fn test_relational_operator_reports_limitation() {
+        let err = parse("parameters.amount < 1000").unwrap_err();
+        assert!(err.contains("not supported"));
+        assert!(err.contains("=="));
+    }
+
+    #[test]
+    fn test_or_falls_back_to_right() {
+        let p = parse(r#"context.role == "guest" || context.role == "admin""#).unwrap();
+        assert!(p.evaluate(&input()).allowed);
+    }
+}
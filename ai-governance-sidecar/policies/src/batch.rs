@@ -0,0 +1,145 @@
+// Batch evaluation with a CloudFormation-Guard-style structured report.
+//
+// A whole agent trajectory can be evaluated in one call: `evaluate_batch`
+// takes a JSON array of `PolicyInput`s and returns an aggregate report listing
+// each per-request decision together with summary counts, rather than a single
+// opaque boolean.
+use serde::Serialize;
+
+use crate::{evaluate_input, scan_findings, PolicyInput, PolicyResult};
+
+/// One result in a batch report: the original request index, an optional
+/// `source` label lifted from `context.source`, the rule/keyword names that
+/// triggered, and the per-request verdict.
+#[derive(Serialize, Debug, Clone)]
+pub struct BatchResult {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub triggered: Vec<String>,
+    pub result: PolicyResult,
+}
+
+/// Summary counts across the whole batch, mirroring Guard's combined output.
+#[derive(Serialize, Debug, Clone)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub allowed: usize,
+    pub denied: usize,
+    pub approval_required: usize,
+}
+
+/// Aggregate outcome of a batch evaluation.
+#[derive(Serialize, Debug, Clone)]
+pub struct BatchReport {
+    pub summary: BatchSummary,
+    pub results: Vec<BatchResult>,
+}
+
+/// Evaluate every request in `inputs` and assemble the combined report.
+pub fn evaluate_batch(inputs: &[PolicyInput]) -> BatchReport {
+    let mut results = Vec::with_capacity(inputs.len());
+    let (mut allowed, mut denied, mut approval_required) = (0, 0, 0);
+
+    for (index, input) in inputs.iter().enumerate() {
+        let result = evaluate_input(input);
+        if result.human_required {
+            approval_required += 1;
+        } else if result.allowed {
+            allowed += 1;
+        } else {
+            denied += 1;
+        }
+        let source = input
+            .context
+            .get("source")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        results.push(BatchResult {
+            index,
+            source,
+            triggered: scan_findings(input),
+            result,
+        });
+    }
+
+    BatchReport {
+        summary: BatchSummary {
+            total: inputs.len(),
+            allowed,
+            denied,
+            approval_required,
+        },
+        results,
+    }
+}
+
+/// WASM export mirroring `evaluate`: read a JSON array of `PolicyInput`s from
+/// guest memory, write the `BatchReport` JSON back, and return the buffer
+/// location to the host.
+///
+/// # Safety
+/// The pointers must describe valid guest memory as set up by the host.
+#[no_mangle]
+pub unsafe extern "C" fn evaluate_batch_export(
+    in_ptr: *const u8,
+    in_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let input = std::slice::from_raw_parts(in_ptr, in_len);
+    let bytes = match serde_json::from_slice::<Vec<PolicyInput>>(input) {
+        Ok(inputs) => {
+            let report = evaluate_batch(&inputs);
+            serde_json::to_vec(&report).unwrap_or_default()
+        }
+        Err(e) => serde_json::to_vec(&PolicyResult::deny(format!("invalid batch JSON: {e}")))
+            .unwrap_or_default(),
+    };
+
+    let len = bytes.len();
+    let ptr = crate::alloc(len);
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, len);
+    *out_ptr = ptr;
+    *out_len = len;
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(tool: &str, action: &str, params: serde_json::Value) -> PolicyInput {
+        PolicyInput {
+            tool: tool.into(),
+            action: action.into(),
+            parameters: params,
+            context: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_batch_counts() {
+        let inputs = vec![
+            input("calc", "add", json!({})),
+            input("api", "fetch", json!({"ssn": "123-45-6789"})),
+        ];
+        let report = evaluate_batch(&inputs);
+        assert_eq!(report.summary.total, 2);
+        assert_eq!(report.summary.allowed, 1);
+        assert_eq!(report.summary.approval_required, 1);
+        // The triggered names flow through on the flagged entry.
+        assert!(!report.results[1].triggered.is_empty());
+        assert_eq!(report.results[1].index, 1);
+    }
+
+    #[test]
+    fn test_source_label_lifted_from_context() {
+        let mut inp = input("calc", "add", json!({}));
+        inp.context = json!({"source": "session-42"});
+        let report = evaluate_batch(&[inp]);
+        assert_eq!(report.summary.allowed, 1);
+        assert_eq!(report.results[0].source.as_deref(), Some("session-42"));
+    }
+}
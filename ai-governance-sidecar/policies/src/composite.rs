@@ -0,0 +1,193 @@
+// Composable policy pipeline.
+//
+// Rather than cramming every check into one `evaluate`, a `CompositePolicy`
+// layers several named sub-evaluators and merges their verdicts with a chosen
+// combinator, modeled on TPM2's policy-step composition. The composition tree
+// is carried in the request `context`, so one WASM binary can be reconfigured
+// without a rebuild.
+use serde::{Deserialize, Serialize};
+
+use crate::iam::PolicyDocument;
+use crate::model::PolicyModel;
+use crate::{default_scan, PolicyInput, PolicyResult};
+
+/// How the verdicts of the sub-policies are merged into one result.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum Combinator {
+    /// Every sub-policy must allow; any deny or approval escalates, and the
+    /// merged confidence is the lowest contributing confidence.
+    AllOf,
+    /// Allow if any sub-policy allows.
+    AnyOf,
+    /// First deny wins, otherwise the highest-confidence approval, otherwise allow.
+    DenyOverrides,
+}
+
+/// A named sub-evaluator in the composition tree.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NamedPolicy {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: SubPolicy,
+}
+
+/// The kinds of sub-policy that can participate in a composition.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", content = "spec", rename_all = "snake_case")]
+pub enum SubPolicy {
+    /// The default keyword + PII scan.
+    Scan,
+    /// A Casbin-style policy model.
+    Model(PolicyModel),
+    /// An IAM-style policy document.
+    Iam(PolicyDocument),
+}
+
+impl SubPolicy {
+    fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        match self {
+            SubPolicy::Scan => default_scan(input),
+            SubPolicy::Model(model) => model.evaluate(input),
+            SubPolicy::Iam(doc) => doc.evaluate(input),
+        }
+    }
+}
+
+/// An ordered set of named sub-policies combined by a single rule.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CompositePolicy {
+    pub combinator: Combinator,
+    pub policies: Vec<NamedPolicy>,
+}
+
+impl CompositePolicy {
+    /// Evaluate every sub-policy and merge the results per the combinator.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        let verdicts: Vec<(String, PolicyResult)> = self
+            .policies
+            .iter()
+            .map(|p| (p.name.clone(), p.kind.evaluate(input)))
+            .collect();
+
+        if verdicts.is_empty() {
+            return PolicyResult::deny("composite policy: no sub-policies configured");
+        }
+
+        match self.combinator {
+            Combinator::AllOf => all_of(&verdicts),
+            Combinator::AnyOf => any_of(&verdicts),
+            Combinator::DenyOverrides => deny_overrides(&verdicts),
+        }
+    }
+}
+
+/// Join the contributing reasons as `name: reason` pairs.
+fn join_reasons(verdicts: &[(String, PolicyResult)]) -> String {
+    verdicts
+        .iter()
+        .map(|(name, r)| format!("{name}: {}", r.reason))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn all_of(verdicts: &[(String, PolicyResult)]) -> PolicyResult {
+    let reason = join_reasons(verdicts);
+    let min_confidence = verdicts
+        .iter()
+        .map(|(_, r)| r.confidence)
+        .fold(f64::INFINITY, f64::min);
+
+    if verdicts.iter().any(|(_, r)| !r.allowed && !r.human_required) {
+        return PolicyResult {
+            confidence: min_confidence,
+            ..PolicyResult::deny(reason)
+        };
+    }
+    if verdicts.iter().any(|(_, r)| r.human_required) {
+        return PolicyResult::require_approval(reason, min_confidence);
+    }
+    PolicyResult {
+        confidence: min_confidence,
+        ..PolicyResult::allow(reason)
+    }
+}
+
+fn any_of(verdicts: &[(String, PolicyResult)]) -> PolicyResult {
+    let reason = join_reasons(verdicts);
+    if verdicts.iter().any(|(_, r)| r.allowed) {
+        return PolicyResult::allow(reason);
+    }
+    if verdicts.iter().any(|(_, r)| r.human_required) {
+        let confidence = verdicts
+            .iter()
+            .filter(|(_, r)| r.human_required)
+            .map(|(_, r)| r.confidence)
+            .fold(0.0, f64::max);
+        return PolicyResult::require_approval(reason, confidence);
+    }
+    PolicyResult::deny(reason)
+}
+
+fn deny_overrides(verdicts: &[(String, PolicyResult)]) -> PolicyResult {
+    let reason = join_reasons(verdicts);
+    if verdicts.iter().any(|(_, r)| !r.allowed && !r.human_required) {
+        return PolicyResult::deny(reason);
+    }
+    match verdicts
+        .iter()
+        .filter(|(_, r)| r.human_required)
+        .map(|(_, r)| r.confidence)
+        .fold(None, |acc: Option<f64>, c| Some(acc.map_or(c, |a| a.max(c))))
+    {
+        Some(confidence) => PolicyResult::require_approval(reason, confidence),
+        None => PolicyResult::allow(reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(params: serde_json::Value) -> PolicyInput {
+        PolicyInput {
+            tool: "api".into(),
+            action: "fetch".into(),
+            parameters: params,
+            context: json!({}),
+        }
+    }
+
+    fn composite(combinator: Combinator) -> CompositePolicy {
+        CompositePolicy {
+            combinator,
+            policies: vec![NamedPolicy {
+                name: "scan".into(),
+                kind: SubPolicy::Scan,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_all_of_escalates_on_approval() {
+        let c = composite(Combinator::AllOf);
+        let r = c.evaluate(&input(json!({"ssn": "123-45-6789"})));
+        assert!(r.human_required);
+    }
+
+    #[test]
+    fn test_all_of_allows_clean() {
+        let c = composite(Combinator::AllOf);
+        let r = c.evaluate(&input(json!({"q": "hello"})));
+        assert!(r.allowed);
+    }
+
+    #[test]
+    fn test_empty_is_deny() {
+        let c = CompositePolicy {
+            combinator: Combinator::AnyOf,
+            policies: vec![],
+        };
+        assert!(!c.evaluate(&input(json!({}))).allowed);
+    }
+}
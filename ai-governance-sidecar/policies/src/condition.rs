@@ -0,0 +1,118 @@
+// Typed condition-operator engine for parameter constraints.
+//
+// A `Conditions` map keys parameter names to a typed `Operation`, each of which
+// returns a boolean when applied to the matching value in
+// `PolicyInput.parameters`. This mirrors how S3 POST-object policies enforce
+// typed field conditions: the first failing condition denies the call, naming
+// the key, the operator and the offending value.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{PolicyInput, PolicyResult};
+
+/// A typed comparison applied to a single parameter value.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "op", content = "value", rename_all = "snake_case")]
+pub enum Operation {
+    Equal(Value),
+    StartsWith(String),
+    LessThan(f64),
+    GreaterThan(f64),
+    In(Vec<Value>),
+}
+
+impl Operation {
+    /// Apply the operation, returning `false` when the value is missing or of
+    /// an incompatible type.
+    pub fn matches(&self, actual: Option<&Value>) -> bool {
+        let actual = match actual {
+            Some(v) => v,
+            None => return false,
+        };
+        match self {
+            Operation::Equal(expected) => actual == expected,
+            Operation::StartsWith(prefix) => actual.as_str().is_some_and(|s| s.starts_with(prefix)),
+            Operation::LessThan(threshold) => actual.as_f64().is_some_and(|n| n < *threshold),
+            Operation::GreaterThan(threshold) => actual.as_f64().is_some_and(|n| n > *threshold),
+            Operation::In(set) => set.contains(actual),
+        }
+    }
+
+    /// Human-readable operator name used in denial reasons.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Operation::Equal(v) => format!("Equal {v}"),
+            Operation::StartsWith(s) => format!("StartsWith \"{s}\""),
+            Operation::LessThan(n) => format!("LessThan {n}"),
+            Operation::GreaterThan(n) => format!("GreaterThan {n}"),
+            Operation::In(vs) => format!("In {vs:?}"),
+        }
+    }
+}
+
+/// A set of conditions keyed by parameter name, all of which must hold.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Conditions(pub HashMap<String, Operation>);
+
+impl Conditions {
+    /// Evaluate every condition, denying on the first failure with a reason
+    /// naming the key, the operator and the offending value.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        for (key, op) in &self.0 {
+            let actual = input.parameters.get(key);
+            if !op.matches(actual) {
+                let found = actual.map_or_else(|| "<missing>".to_string(), |v| v.to_string());
+                return PolicyResult::deny(format!(
+                    "condition failed: `{key}` {} (found {found})",
+                    op.describe()
+                ));
+            }
+        }
+        PolicyResult::allow("all parameter conditions satisfied")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(params: Value) -> PolicyInput {
+        PolicyInput {
+            tool: "upload".into(),
+            action: "put".into(),
+            parameters: params,
+            context: json!({}),
+        }
+    }
+
+    fn conditions() -> Conditions {
+        Conditions(HashMap::from([
+            ("content-type".into(), Operation::StartsWith("image/".into())),
+            ("count".into(), Operation::LessThan(1000.0)),
+        ]))
+    }
+
+    #[test]
+    fn test_all_satisfied() {
+        let r = conditions().evaluate(&input(json!({"content-type": "image/png", "count": 10})));
+        assert!(r.allowed);
+    }
+
+    #[test]
+    fn test_first_failure_named() {
+        let r = conditions().evaluate(&input(json!({"content-type": "text/plain", "count": 10})));
+        assert!(!r.allowed);
+        assert!(r.reason.contains("content-type"));
+        assert!(r.reason.contains("StartsWith"));
+    }
+
+    #[test]
+    fn test_numeric_range() {
+        let r = conditions().evaluate(&input(json!({"content-type": "image/png", "count": 5000})));
+        assert!(!r.allowed);
+        assert!(r.reason.contains("count"));
+    }
+}
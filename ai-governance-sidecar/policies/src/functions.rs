@@ -0,0 +1,229 @@
+// Named in-policy functions usable inside conditions.
+//
+// Raw keyword matching misses obfuscated payloads: `DROP   TABLE` with runs of
+// whitespace, or mixed-case `Drop Table`, slip past a literal `contains`. A
+// small set of function expressions normalizes a `PolicyInput` value before it
+// reaches the operator comparison — `regex_replace`, `to_lower`, `json_path`
+// and `contains_any` (generalizing `contains_sensitive_keywords`). Expressions
+// nest, resolving against the request's JSON and yielding a derived `Value`
+// that a typed `Operation` then compares.
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::condition::Operation;
+use crate::{PolicyInput, PolicyResult};
+
+/// A value-producing expression tree. Leaves pull an attribute or a literal
+/// from the request; the remaining variants are functions that transform the
+/// value of their nested `input` expression.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "fn", rename_all = "snake_case")]
+pub enum ValueExpr {
+    /// A dotted path into the request (`parameters.*`, `context.*`, `tool`,
+    /// `action`).
+    Field { path: String },
+    /// A constant value.
+    Literal { value: Value },
+    /// Lowercase a string value.
+    ToLower { input: Box<ValueExpr> },
+    /// Replace every match of `pattern` in a string value with `replacement`.
+    RegexReplace {
+        input: Box<ValueExpr>,
+        pattern: String,
+        replacement: String,
+    },
+    /// Resolve a dotted path within an already-derived value.
+    JsonPath { input: Box<ValueExpr>, path: String },
+    /// `true` when the string value contains any of `needles`.
+    ContainsAny {
+        input: Box<ValueExpr>,
+        needles: Vec<String>,
+    },
+}
+
+impl ValueExpr {
+    /// Resolve the expression against the request, returning `Value::Null` when
+    /// a path is missing or a function is applied to an incompatible type
+    /// (`ContainsAny` on a non-string value yields `false` instead).
+    pub fn resolve(&self, input: &PolicyInput) -> Value {
+        match self {
+            ValueExpr::Field { path } => resolve_path(input, path).unwrap_or(Value::Null),
+            ValueExpr::Literal { value } => value.clone(),
+            ValueExpr::ToLower { input: inner } => match inner.resolve(input) {
+                Value::String(s) => Value::String(s.to_lowercase()),
+                _ => Value::Null,
+            },
+            ValueExpr::RegexReplace {
+                input: inner,
+                pattern,
+                replacement,
+            } => match (inner.resolve(input).as_str(), compiled(pattern)) {
+                (Some(s), Some(re)) => Value::String(re.replace_all(s, replacement.as_str()).into_owned()),
+                _ => Value::Null,
+            },
+            ValueExpr::JsonPath { input: inner, path } => {
+                let mut current = inner.resolve(input);
+                for part in path.split('.') {
+                    current = match current.get(part) {
+                        Some(v) => v.clone(),
+                        None => return Value::Null,
+                    };
+                }
+                current
+            }
+            ValueExpr::ContainsAny { input: inner, needles } => match inner.resolve(input).as_str() {
+                Some(s) => Value::Bool(needles.iter().any(|n| s.contains(n.as_str()))),
+                None => Value::Bool(false),
+            },
+        }
+    }
+}
+
+/// A condition that applies a typed `Operation` to the derived value of a
+/// function expression.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FnCondition {
+    pub input: ValueExpr,
+    #[serde(flatten)]
+    pub op: Operation,
+}
+
+/// An ordered set of function-backed conditions, all of which must hold.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FnConditions(pub Vec<FnCondition>);
+
+impl FnConditions {
+    /// Evaluate every condition, denying on the first failure with a reason
+    /// naming the derived value and the operator.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        for cond in &self.0 {
+            let derived = cond.input.resolve(input);
+            if !cond.op.matches(Some(&derived)) {
+                return PolicyResult::deny(format!(
+                    "function condition failed: derived value `{derived}` did not satisfy {}",
+                    cond.op.describe()
+                ));
+            }
+        }
+        PolicyResult::allow("all function conditions satisfied")
+    }
+}
+
+/// Compile `pattern` with a shared cache and conservative size limits so a
+/// pathological regex cannot blow up compile time or memory. Returns `None`
+/// when the pattern is too long or fails to compile within the limits.
+fn compiled(pattern: &str) -> Option<Regex> {
+    const MAX_PATTERN_LEN: usize = 512;
+    const MAX_CACHE: usize = 128;
+    const SIZE_LIMIT: usize = 1 << 20;
+
+    if pattern.len() > MAX_PATTERN_LEN {
+        return None;
+    }
+
+    static CACHE: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(|| Mutex::new(HashMap::new())).lock().ok()?;
+    if let Some(re) = cache.get(pattern) {
+        return Some(re.clone());
+    }
+    let re = RegexBuilder::new(pattern)
+        .size_limit(SIZE_LIMIT)
+        .dfa_size_limit(SIZE_LIMIT)
+        .build()
+        .ok()?;
+    if cache.len() < MAX_CACHE {
+        cache.insert(pattern.to_string(), re.clone());
+    }
+    Some(re)
+}
+
+/// Resolve a dotted path against the request's attributes.
+fn resolve_path(input: &PolicyInput, path: &str) -> Option<Value> {
+    let mut parts = path.split('.');
+    let head = parts.next()?;
+    let mut current = match head {
+        "parameters" => input.parameters.clone(),
+        "context" => input.context.clone(),
+        "tool" => return Some(Value::String(input.tool.clone())),
+        "action" => return Some(Value::String(input.action.clone())),
+        _ => return None,
+    };
+    for part in parts {
+        current = current.get(part)?.clone();
+    }
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(params: Value) -> PolicyInput {
+        PolicyInput {
+            tool: "db".into(),
+            action: "query".into(),
+            parameters: params,
+            context: json!({}),
+        }
+    }
+
+    fn field(path: &str) -> Box<ValueExpr> {
+        Box::new(ValueExpr::Field { path: path.into() })
+    }
+
+    #[test]
+    fn test_regex_replace_normalizes_whitespace() {
+        let expr = ValueExpr::RegexReplace {
+            input: field("parameters.sql"),
+            pattern: r"\s+".into(),
+            replacement: " ".into(),
+        };
+        let out = expr.resolve(&input(json!({"sql": "DROP    TABLE users"})));
+        assert_eq!(out, json!("DROP TABLE users"));
+    }
+
+    #[test]
+    fn test_to_lower() {
+        let expr = ValueExpr::ToLower { input: field("parameters.verb") };
+        assert_eq!(expr.resolve(&input(json!({"verb": "SELECT"}))), json!("select"));
+    }
+
+    #[test]
+    fn test_contains_any() {
+        let expr = ValueExpr::ContainsAny {
+            input: field("parameters.text"),
+            needles: vec!["secret".into(), "token".into()],
+        };
+        assert_eq!(expr.resolve(&input(json!({"text": "the token is X"}))), json!(true));
+    }
+
+    #[test]
+    fn test_obfuscated_sql_caught_after_normalization() {
+        // Normalize whitespace and case, then require the statement to start
+        // with `select`.
+        let conds = FnConditions(vec![FnCondition {
+            input: ValueExpr::ToLower {
+                input: Box::new(ValueExpr::RegexReplace {
+                    input: field("parameters.sql"),
+                    pattern: r"\s+".into(),
+                    replacement: " ".into(),
+                }),
+            },
+            op: Operation::StartsWith("select".into()),
+        }]);
+        let denied = conds.evaluate(&input(json!({"sql": "DROP   TABLE users"})));
+        assert!(!denied.allowed);
+        let ok = conds.evaluate(&input(json!({"sql": "SELECT * FROM t"})));
+        assert!(ok.allowed);
+    }
+
+    #[test]
+    fn test_overlong_pattern_rejected() {
+        assert!(compiled(&"a".repeat(1024)).is_none());
+    }
+}
@@ -0,0 +1,188 @@
+// IAM-style declarative policy documents.
+//
+// A `PolicyDocument` is a list of statements using the familiar
+// Effect/Action/Resource/Condition grammar. Actions are glob-matched against
+// `PolicyInput.action`, resources against `PolicyInput.tool`, and conditions
+// against entries of `PolicyInput.parameters`. Statements are resolved with
+// explicit-deny-overrides semantics and an implicit deny when nothing matches.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::glob_match;
+use crate::{PolicyInput, PolicyResult};
+
+/// A declarative policy document: an ordered list of statements.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PolicyDocument {
+    pub statements: Vec<Statement>,
+}
+
+/// The effect a matching statement contributes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Allow,
+    Deny,
+    RequireApproval,
+}
+
+/// A single policy statement.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Statement {
+    pub effect: Effect,
+    /// Glob patterns matched against `PolicyInput.action`.
+    pub action: Vec<String>,
+    /// Glob patterns matched against `PolicyInput.tool`.
+    pub resource: Vec<String>,
+    /// Conditions keyed by parameter name, applied to `PolicyInput.parameters`.
+    #[serde(default)]
+    pub conditions: HashMap<String, Condition>,
+}
+
+/// A typed condition on a single parameter value.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "operator", content = "value")]
+pub enum Condition {
+    Equal(Value),
+    NotEqual(Value),
+    StringStartsWith(String),
+    StringContains(String),
+    NumericGreaterThanEquals(f64),
+}
+
+impl Condition {
+    /// Evaluate the condition against a parameter value, returning `false` when
+    /// the value is absent or of the wrong type.
+    fn matches(&self, actual: Option<&Value>) -> bool {
+        let actual = match actual {
+            Some(v) => v,
+            None => return false,
+        };
+        match self {
+            Condition::Equal(expected) => actual == expected,
+            Condition::NotEqual(expected) => actual != expected,
+            Condition::StringStartsWith(prefix) => {
+                actual.as_str().is_some_and(|s| s.starts_with(prefix))
+            }
+            Condition::StringContains(needle) => {
+                actual.as_str().is_some_and(|s| s.contains(needle))
+            }
+            Condition::NumericGreaterThanEquals(threshold) => {
+                actual.as_f64().is_some_and(|n| n >= *threshold)
+            }
+        }
+    }
+}
+
+impl Statement {
+    /// Whether this statement matches the request: action and resource globs
+    /// plus every configured condition.
+    fn matches(&self, input: &PolicyInput) -> bool {
+        let action_ok = self.action.iter().any(|p| glob_match(p, &input.action));
+        let resource_ok = self.resource.iter().any(|p| glob_match(p, &input.tool));
+        if !action_ok || !resource_ok {
+            return false;
+        }
+        self.conditions
+            .iter()
+            .all(|(key, cond)| cond.matches(input.parameters.get(key)))
+    }
+}
+
+impl PolicyDocument {
+    /// Evaluate the document with explicit-deny-overrides resolution: a matching
+    /// `Deny` wins, else a matching `RequireApproval` sets `human_required`, else
+    /// a matching `Allow` permits, else an implicit deny.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        let mut approval = false;
+        let mut allow = false;
+        for statement in &self.statements {
+            if !statement.matches(input) {
+                continue;
+            }
+            match statement.effect {
+                Effect::Deny => {
+                    return PolicyResult::deny("IAM policy: matched explicit Deny statement")
+                }
+                Effect::RequireApproval => approval = true,
+                Effect::Allow => allow = true,
+            }
+        }
+        if approval {
+            PolicyResult::require_approval("IAM policy: matched RequireApproval statement", 0.9)
+        } else if allow {
+            PolicyResult::allow("IAM policy: matched Allow statement")
+        } else {
+            PolicyResult::deny("IAM policy: no matching statement (implicit deny)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(tool: &str, action: &str, params: Value) -> PolicyInput {
+        PolicyInput {
+            tool: tool.into(),
+            action: action.into(),
+            parameters: params,
+            context: json!({}),
+        }
+    }
+
+    fn doc() -> PolicyDocument {
+        PolicyDocument {
+            statements: vec![
+                Statement {
+                    effect: Effect::Allow,
+                    action: vec!["read*".into()],
+                    resource: vec!["db_*".into()],
+                    conditions: HashMap::new(),
+                },
+                Statement {
+                    effect: Effect::RequireApproval,
+                    action: vec!["*".into()],
+                    resource: vec!["db_*".into()],
+                    conditions: HashMap::from([(
+                        "count".into(),
+                        Condition::NumericGreaterThanEquals(100.0),
+                    )]),
+                },
+                Statement {
+                    effect: Effect::Deny,
+                    action: vec!["drop".into()],
+                    resource: vec!["*".into()],
+                    conditions: HashMap::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_allow() {
+        let r = doc().evaluate(&input("db_users", "read", json!({"count": 5})));
+        assert!(r.allowed);
+    }
+
+    #[test]
+    fn test_bulk_requires_approval() {
+        let r = doc().evaluate(&input("db_users", "update", json!({"count": 1000})));
+        assert!(r.human_required);
+    }
+
+    #[test]
+    fn test_deny_overrides() {
+        let r = doc().evaluate(&input("db_users", "drop", json!({"count": 1000})));
+        assert!(!r.allowed);
+        assert!(!r.human_required);
+    }
+
+    #[test]
+    fn test_implicit_deny() {
+        let r = doc().evaluate(&input("cache", "read", json!({})));
+        assert!(!r.allowed);
+    }
+}
@@ -2,6 +2,22 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub mod abac;
+pub mod batch;
+pub mod composite;
+pub mod condition;
+pub mod functions;
+pub mod iam;
+pub mod model;
+pub mod pii;
+pub mod policy_doc;
+pub mod report;
+pub mod signed;
+pub mod signing;
+
+use iam::PolicyDocument;
+use model::PolicyModel;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PolicyInput {
     pub tool: String,
@@ -16,6 +32,19 @@ pub struct PolicyResult {
     pub human_required: bool,
     pub reason: String,
     pub confidence: f64,
+    /// Detached signature over the canonical decision payload. Absent unless a
+    /// signing key has been loaded, so unsigned builds stay byte-compatible
+    /// with the original length-prefixed output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Identifier of the key that produced `signature`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    /// Monotonic nonce folded into the signed payload. Emitted alongside
+    /// `signature` so the host can reconstruct the signed bytes and detect
+    /// replay; absent on unsigned results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
 }
 
 impl PolicyResult {
@@ -25,24 +54,33 @@ impl PolicyResult {
             human_required: false,
             reason: reason.into(),
             confidence: 1.0,
+            signature: None,
+            key_id: None,
+            nonce: None,
         }
     }
-    
+
     pub fn deny(reason: impl Into<String>) -> Self {
         Self {
             allowed: false,
             human_required: false,
             reason: reason.into(),
             confidence: 1.0,
+            signature: None,
+            key_id: None,
+            nonce: None,
         }
     }
-    
+
     pub fn require_approval(reason: impl Into<String>, confidence: f64) -> Self {
         Self {
             allowed: false,
             human_required: true,
             reason: reason.into(),
             confidence,
+            signature: None,
+            key_id: None,
+            nonce: None,
         }
     }
 }
@@ -76,10 +114,7 @@ pub extern "C" fn evaluate(
     // 2) Parse request and run *minimal* logic (passthrough by default)
     //    Replace this with your real policy checks as you evolve.
     let result: PolicyResult = match serde_json::from_slice::<PolicyInput>(input) {
-        Ok(_inp) => {
-            // Example: always allow (passthrough)
-            PolicyResult::allow("passthrough")
-        }
+        Ok(inp) => evaluate_input(&inp),
         Err(e) => PolicyResult::deny(format!("invalid JSON: {e}")),
     };
 
@@ -106,6 +141,56 @@ pub extern "C" fn evaluate(
     0
 }
 
+/// Evaluate a single request: run the engine configured in `context` (see
+/// `decide`) when present, otherwise the default keyword/PII scan, then attach
+/// a signature if a signing key has been loaded.
+pub fn evaluate_input(input: &PolicyInput) -> PolicyResult {
+    signing::sign_result(decide(input), input)
+}
+
+/// Produce the unsigned decision for a request, selecting the configured
+/// engine from `context` or falling back to the default scan.
+pub fn decide(input: &PolicyInput) -> PolicyResult {
+    if let Some(raw) = input.context.get("composite") {
+        match serde_json::from_value::<composite::CompositePolicy>(raw.clone()) {
+            Ok(tree) => tree.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid composite policy: {e}")),
+        }
+    } else if let Some(raw) = input.context.get("model") {
+        match serde_json::from_value::<PolicyModel>(raw.clone()) {
+            Ok(model) => model.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid policy model: {e}")),
+        }
+    } else if let Some(raw) = input.context.get("policy") {
+        match serde_json::from_value::<PolicyDocument>(raw.clone()) {
+            Ok(doc) => doc.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid policy document: {e}")),
+        }
+    } else if let Some(raw) = input.context.get("conditions") {
+        match serde_json::from_value::<condition::Conditions>(raw.clone()) {
+            Ok(conds) => conds.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid conditions: {e}")),
+        }
+    } else if let Some(raw) = input.context.get("fn_conditions") {
+        match serde_json::from_value::<functions::FnConditions>(raw.clone()) {
+            Ok(conds) => conds.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid function conditions: {e}")),
+        }
+    } else if let Some(expr) = input.context.get("abac").and_then(|v| v.as_str()) {
+        match abac::parse(expr) {
+            Ok(policy) => policy.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid ABAC expression: {e}")),
+        }
+    } else if let Some(raw) = input.context.get("document") {
+        match serde_json::from_value::<policy_doc::PolicyDocument>(raw.clone()) {
+            Ok(doc) => doc.evaluate(input),
+            Err(e) => PolicyResult::deny(format!("invalid policy document: {e}")),
+        }
+    } else {
+        default_scan(input)
+    }
+}
+
 // Serialize result to JSON and return pointer
 pub fn serialize_result(result: &PolicyResult) -> *mut u8 {
     let json = serde_json::to_string(result).unwrap_or_else(|e| {
@@ -118,6 +203,47 @@ pub fn serialize_result(result: &PolicyResult) -> *mut u8 {
     ptr
 }
 
+/// Sensitive keywords scanned by the default (no-model) evaluation path.
+const SENSITIVE_KEYWORDS: &[&str] = &[
+    "ssn", "social security", "credit card", "password", "api key", "secret",
+    "private key", "token", "credentials", "bank account", "routing number",
+];
+
+/// Default evaluation when `context` configures no policy engine: combine a
+/// sensitive-keyword scan with regex/Luhn PII detection over the request, and
+/// require human approval when either finds anything. The matched category
+/// names are surfaced in `reason`, and `confidence` grows with the number of
+/// findings.
+pub(crate) fn default_scan(input: &PolicyInput) -> PolicyResult {
+    let findings = scan_findings(input);
+
+    if findings.is_empty() {
+        return PolicyResult::allow("passthrough");
+    }
+
+    // Confidence scales with the number of distinct findings, capped at 0.99.
+    let confidence = (0.8 + 0.05 * findings.len() as f64).min(0.99);
+    PolicyResult::require_approval(
+        format!("sensitive data detected: {}", findings.join(", ")),
+        confidence,
+    )
+}
+
+/// Distinct keyword and PII-category names triggered by the default scan over a
+/// request. Shared by `default_scan` and the batch report so both surface the
+/// same triggered names.
+pub(crate) fn scan_findings(input: &PolicyInput) -> Vec<String> {
+    let haystack = format!("{} {} {}", input.tool, input.action, input.parameters);
+    let mut findings = contains_sensitive_keywords(&haystack, SENSITIVE_KEYWORDS);
+    for m in pii::detect_pii(&haystack) {
+        let category = m.category.to_string();
+        if !findings.contains(&category) {
+            findings.push(category);
+        }
+    }
+    findings
+}
+
 // Helper to check for sensitive keywords
 pub fn contains_sensitive_keywords(text: &str, keywords: &[&str]) -> Vec<String> {
     let text_lower = text.to_lowercase();
@@ -0,0 +1,536 @@
+// Casbin-style PERM policy model engine.
+//
+// Instead of hardcoding keyword lists in `evaluate`, a caller can ship a
+// loadable model describing a request definition, a policy definition, a set
+// of policy rules, a matcher expression and an effect-resolution rule. The
+// model is parsed once and every policy line is run through the matcher
+// against the incoming request; the matched effects are then resolved into an
+// `allowed` / `human_required` verdict.
+use serde::{Deserialize, Serialize};
+
+use crate::{PolicyInput, PolicyResult};
+
+/// A Casbin PERM model: request definition, policy definition, the policy
+/// rules themselves, the matcher expression and the effect rule.
+///
+/// `request_def` and `policy_def` list the named tokens in order, so the
+/// matcher can bind `r.tool`/`p.tool_pattern` to the right positional value.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PolicyModel {
+    /// Request tokens, e.g. `["tool", "action", "parameters", "context"]`.
+    pub request_def: Vec<String>,
+    /// Policy tokens, e.g. `["effect", "tool_pattern", "action_pattern", "param_matcher"]`.
+    pub policy_def: Vec<String>,
+    /// Each rule is a positional list aligned with `policy_def`.
+    pub policies: Vec<Vec<String>>,
+    /// Boolean matcher expression evaluated per policy rule.
+    pub matcher: String,
+    /// Effect-resolution rule built from the clauses
+    /// `some(where p.eft == allow)` and `!some(where p.eft == deny)`, either
+    /// alone or joined with `&&`, extended with the `approve` effect which maps
+    /// to `human_required`.
+    pub effect: String,
+}
+
+/// The effect a matched policy rule contributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Allow,
+    Deny,
+    Approve,
+}
+
+impl Effect {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "allow" => Some(Effect::Allow),
+            "deny" => Some(Effect::Deny),
+            "approve" => Some(Effect::Approve),
+            _ => None,
+        }
+    }
+}
+
+/// The effect-resolution rule parsed from the model's `effect` string. Two
+/// clauses are recognised: `some(where p.eft == allow)` requires a matched
+/// allow, and `!some(where p.eft == deny)` lets a matched deny override. A rule
+/// that omits the allow clause permits by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EffectRule {
+    allow_required: bool,
+    deny_overrides: bool,
+}
+
+impl EffectRule {
+    /// Parse the supported Casbin effect forms, ignoring whitespace. Returns an
+    /// error for any clause the engine does not implement so a mistyped rule is
+    /// surfaced rather than silently treated as deny-overrides.
+    fn parse(raw: &str) -> Result<Self, String> {
+        let normalized: String = raw.split_whitespace().collect();
+        let mut allow_required = false;
+        let mut deny_overrides = false;
+        let mut rest = normalized.as_str();
+        while !rest.is_empty() {
+            let (clause, tail) = match rest.split_once("&&") {
+                Some((c, t)) => (c, t),
+                None => (rest, ""),
+            };
+            match clause {
+                "some(wherep.eft==allow)" => allow_required = true,
+                "!some(wherep.eft==deny)" => deny_overrides = true,
+                other => return Err(format!("unsupported effect clause: {other}")),
+            }
+            rest = tail;
+        }
+        if !allow_required && !deny_overrides {
+            return Err("effect rule has no recognised clause".into());
+        }
+        Ok(Self {
+            allow_required,
+            deny_overrides,
+        })
+    }
+}
+
+impl PolicyModel {
+    /// Evaluate `input` against every policy rule and resolve the final verdict.
+    /// The matcher is tokenized once and reused for every policy line.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        let rule = match EffectRule::parse(&self.effect) {
+            Ok(r) => r,
+            Err(e) => return PolicyResult::deny(format!("invalid effect rule: {e}")),
+        };
+        let matcher = match Matcher::new(&self.matcher) {
+            Ok(m) => m,
+            Err(e) => return PolicyResult::deny(format!("model matcher error: {e}")),
+        };
+        let mut matched = Vec::new();
+        for policy in &self.policies {
+            let binding = Binding {
+                model: self,
+                request: input,
+                rule: policy,
+            };
+            match matcher.eval(&binding) {
+                Ok(true) => {
+                    if let Some(eft) = policy.first().and_then(|e| Effect::parse(e)) {
+                        matched.push(eft);
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => return PolicyResult::deny(format!("model matcher error: {e}")),
+            }
+        }
+        self.resolve(rule, &matched)
+    }
+
+    /// Resolve matched effects according to the parsed `effect` rule, mapping
+    /// the `approve` effect to `human_required`.
+    fn resolve(&self, rule: EffectRule, matched: &[Effect]) -> PolicyResult {
+        if rule.deny_overrides && matched.contains(&Effect::Deny) {
+            return PolicyResult::deny("policy model: matched deny rule");
+        }
+        if matched.contains(&Effect::Approve) {
+            return PolicyResult::require_approval("policy model: matched approve rule", 0.9);
+        }
+        if rule.allow_required {
+            if matched.contains(&Effect::Allow) {
+                return PolicyResult::allow("policy model: matched allow rule");
+            }
+            return PolicyResult::deny("policy model: no matching policy (implicit deny)");
+        }
+        // No allow clause: permit unless a deny overrode above.
+        PolicyResult::allow("policy model: permitted by default")
+    }
+
+    /// Position of a policy token within `policy_def`.
+    fn policy_index(&self, token: &str) -> Option<usize> {
+        self.policy_def.iter().position(|t| t == token)
+    }
+
+    /// Whether `token` is a declared request token in `request_def`.
+    fn request_declares(&self, token: &str) -> bool {
+        self.request_def.iter().any(|t| t == token)
+    }
+}
+
+/// Binds `r.*` and `p.*` references to concrete values for one policy rule.
+struct Binding<'a> {
+    model: &'a PolicyModel,
+    request: &'a PolicyInput,
+    rule: &'a [String],
+}
+
+impl Binding<'_> {
+    /// Resolve a dotted reference such as `r.tool` or `p.tool_pattern` to its
+    /// string value.
+    fn resolve(&self, reference: &str) -> Result<String, String> {
+        let (scope, field) = reference
+            .split_once('.')
+            .ok_or_else(|| format!("not a field reference: {reference}"))?;
+        match scope {
+            "r" => {
+                if !self.model.request_declares(field) {
+                    return Err(format!("r.{field} is not declared in request_def"));
+                }
+                match field {
+                    "tool" => Ok(self.request.tool.clone()),
+                    "action" => Ok(self.request.action.clone()),
+                    "parameters" => Ok(self.request.parameters.to_string()),
+                    "context" => Ok(self.request.context.to_string()),
+                    other => Err(format!("unknown request field: r.{other}")),
+                }
+            }
+            "p" => {
+                let idx = self
+                    .model
+                    .policy_index(field)
+                    .ok_or_else(|| format!("unknown policy field: p.{field}"))?;
+                self.rule
+                    .get(idx)
+                    .cloned()
+                    .ok_or_else(|| format!("policy rule missing field p.{field}"))
+            }
+            other => Err(format!("unknown scope: {other}")),
+        }
+    }
+}
+
+/// A minimal recursive-descent evaluator for matcher expressions supporting
+/// `==`, `!=`, `keyMatch` glob wildcards, `&&`, `||`, `!`, parentheses, field
+/// references and string literals.
+struct Matcher {
+    tokens: Vec<Token>,
+}
+
+impl Matcher {
+    /// Tokenize the matcher expression once; the resulting `Matcher` is reused
+    /// across every policy rule.
+    fn new(src: &str) -> Result<Self, String> {
+        Ok(Self {
+            tokens: tokenize(src)?,
+        })
+    }
+
+    fn eval(&self, binding: &Binding) -> Result<bool, String> {
+        let mut parser = Parser {
+            tokens: &self.tokens,
+            pos: 0,
+            binding,
+        };
+        let value = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(format!("unexpected token near {:?}", parser.tokens.get(parser.pos)));
+        }
+        Ok(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    And,
+    Or,
+    Not,
+    Eq,
+    Ne,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = src.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ws if ws.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Eq);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ne);
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '"' | '\'' => {
+                let quote = c;
+                i += 1;
+                let mut s = String::new();
+                while i < chars.len() && chars[i] != quote {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err("unterminated string literal".into());
+                }
+                i += 1; // closing quote
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut s = String::new();
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                tokens.push(Token::Ident(s));
+            }
+            other => return Err(format!("unexpected character: {other}")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    binding: &'a Binding<'a>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&Token> {
+        let t = self.tokens.get(self.pos);
+        self.pos += 1;
+        t
+    }
+
+    fn parse_or(&mut self) -> Result<bool, String> {
+        let mut value = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            value = value || rhs;
+        }
+        Ok(value)
+    }
+
+    fn parse_and(&mut self) -> Result<bool, String> {
+        let mut value = self.parse_not()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let rhs = self.parse_not()?;
+            value = value && rhs;
+        }
+        Ok(value)
+    }
+
+    fn parse_not(&mut self) -> Result<bool, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(!self.parse_not()?);
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<bool, String> {
+        match self.peek().cloned() {
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let value = self.parse_or()?;
+                if self.bump() != Some(&Token::RParen) {
+                    return Err("expected closing parenthesis".into());
+                }
+                Ok(value)
+            }
+            Some(Token::Ident(name)) if self.tokens.get(self.pos + 1) == Some(&Token::LParen) => {
+                self.parse_call(&name)
+            }
+            _ => {
+                // A comparison between two operands.
+                let lhs = self.parse_operand()?;
+                match self.peek().cloned() {
+                    Some(Token::Eq) => {
+                        self.pos += 1;
+                        let rhs = self.parse_operand()?;
+                        Ok(lhs == rhs)
+                    }
+                    Some(Token::Ne) => {
+                        self.pos += 1;
+                        let rhs = self.parse_operand()?;
+                        Ok(lhs != rhs)
+                    }
+                    _ => Err("expected comparison operator".into()),
+                }
+            }
+        }
+    }
+
+    fn parse_call(&mut self, name: &str) -> Result<bool, String> {
+        self.pos += 2; // consume ident and '('
+        let first = self.parse_operand()?;
+        if self.bump() != Some(&Token::Comma) {
+            return Err(format!("{name} expects two arguments"));
+        }
+        let second = self.parse_operand()?;
+        if self.bump() != Some(&Token::RParen) {
+            return Err(format!("{name}: expected closing parenthesis"));
+        }
+        match name {
+            "keyMatch" => Ok(key_match(&first, &second)),
+            other => Err(format!("unknown function: {other}")),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<String, String> {
+        match self.bump().cloned() {
+            Some(Token::Str(s)) => Ok(s),
+            Some(Token::Ident(name)) => {
+                if name.contains('.') {
+                    self.binding.resolve(&name)
+                } else {
+                    // Bare identifier: treat as a literal (e.g. effect names).
+                    Ok(name)
+                }
+            }
+            other => Err(format!("expected operand, found {other:?}")),
+        }
+    }
+}
+
+/// Casbin-style `keyMatch`: `*` matches any run of characters.
+fn key_match(value: &str, pattern: &str) -> bool {
+    glob_match(pattern, value)
+}
+
+/// Simple glob matcher where `*` matches zero or more characters and `?`
+/// matches a single character.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let (mut star, mut mark) = (None, 0usize);
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some(pi);
+            mark = ti;
+            pi += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            mark += 1;
+            ti = mark;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn model() -> PolicyModel {
+        PolicyModel {
+            request_def: vec!["tool".into(), "action".into(), "parameters".into(), "context".into()],
+            policy_def: vec!["effect".into(), "tool_pattern".into(), "action_pattern".into()],
+            policies: vec![
+                vec!["allow".into(), "db_*".into(), "read".into()],
+                vec!["deny".into(), "db_*".into(), "drop".into()],
+                vec!["approve".into(), "*".into(), "delete".into()],
+            ],
+            matcher: "keyMatch(r.tool, p.tool_pattern) && keyMatch(r.action, p.action_pattern)"
+                .into(),
+            effect: "some(where p.eft == allow) && !some(where p.eft == deny)".into(),
+        }
+    }
+
+    fn input(tool: &str, action: &str) -> PolicyInput {
+        PolicyInput {
+            tool: tool.into(),
+            action: action.into(),
+            parameters: json!({}),
+            context: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_allow_match() {
+        let r = model().evaluate(&input("db_users", "read"));
+        assert!(r.allowed);
+    }
+
+    #[test]
+    fn test_deny_overrides() {
+        let r = model().evaluate(&input("db_users", "drop"));
+        assert!(!r.allowed);
+        assert!(!r.human_required);
+    }
+
+    #[test]
+    fn test_approve_maps_to_human_required() {
+        let r = model().evaluate(&input("files", "delete"));
+        assert!(r.human_required);
+    }
+
+    #[test]
+    fn test_implicit_deny() {
+        let r = model().evaluate(&input("calculator", "add"));
+        assert!(!r.allowed);
+    }
+
+    #[test]
+    fn test_deny_override_clause_honored() {
+        // Without the allow clause the rule permits by default, but a matched
+        // deny still overrides.
+        let mut m = model();
+        m.effect = "!some(where p.eft == deny)".into();
+        assert!(m.evaluate(&input("calculator", "add")).allowed);
+        assert!(!m.evaluate(&input("db_users", "drop")).allowed);
+    }
+
+    #[test]
+    fn test_unsupported_effect_rule_denies() {
+        let mut m = model();
+        m.effect = "priority(p.eft) || deny".into();
+        let r = m.evaluate(&input("db_users", "read"));
+        assert!(!r.allowed);
+        assert!(r.reason.contains("invalid effect rule"));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("db_*", "db_users"));
+        assert!(!glob_match("db_*", "cache_users"));
+        assert!(glob_match("*", "anything"));
+    }
+}
@@ -0,0 +1,130 @@
+// Regex + Luhn based PII detection.
+//
+// Field-name matching (`params_str.contains("ssn")`) misses sensitive values
+// that appear inside free text, and never validates candidate card numbers.
+// `detect_pii` runs compiled regexes for several PII categories and confirms
+// card candidates with the Luhn checksum so that real card numbers are caught
+// while random digit strings of the same length are not.
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// A single detected PII value with its category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiMatch {
+    pub category: &'static str,
+    pub value: String,
+}
+
+struct Patterns {
+    ssn: Regex,
+    phone: Regex,
+    email: Regex,
+    ipv4: Regex,
+    card_candidate: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        ssn: Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap(),
+        phone: Regex::new(r"\b(?:\+?1[-.\s]?)?\(?\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}\b").unwrap(),
+        email: Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b").unwrap(),
+        ipv4: Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b").unwrap(),
+        card_candidate: Regex::new(r"\b\d{13,19}\b").unwrap(),
+    })
+}
+
+/// Scan `text` for PII, returning one `PiiMatch` per detected value. Card
+/// candidates are only reported when they pass the Luhn checksum.
+pub fn detect_pii(text: &str) -> Vec<PiiMatch> {
+    let p = patterns();
+    let mut matches = Vec::new();
+
+    for m in p.ssn.find_iter(text) {
+        matches.push(PiiMatch { category: "ssn", value: m.as_str().to_string() });
+    }
+    for m in p.email.find_iter(text) {
+        matches.push(PiiMatch { category: "email", value: m.as_str().to_string() });
+    }
+    for m in p.ipv4.find_iter(text) {
+        matches.push(PiiMatch { category: "ipv4", value: m.as_str().to_string() });
+    }
+    // Phone detection skips anything already claimed as an SSN (overlapping
+    // digit shapes) to avoid double-reporting.
+    for m in p.phone.find_iter(text) {
+        if p.ssn.is_match(m.as_str()) {
+            continue;
+        }
+        matches.push(PiiMatch { category: "phone", value: m.as_str().to_string() });
+    }
+    for m in p.card_candidate.find_iter(text) {
+        if luhn_valid(m.as_str()) {
+            matches.push(PiiMatch { category: "credit_card", value: m.as_str().to_string() });
+        }
+    }
+    matches
+}
+
+/// Validate a string of digits with the Luhn checksum: walking right-to-left,
+/// double every second digit (subtracting 9 when the result exceeds 9), sum
+/// everything, and accept only when the total is a multiple of ten.
+pub fn luhn_valid(digits: &str) -> bool {
+    let mut sum = 0u32;
+    let mut count = 0u32;
+    for c in digits.chars().rev() {
+        let Some(d) = c.to_digit(10) else { return false };
+        let v = if count % 2 == 1 {
+            let doubled = d * 2;
+            if doubled > 9 {
+                doubled - 9
+            } else {
+                doubled
+            }
+        } else {
+            d
+        };
+        sum += v;
+        count += 1;
+    }
+    count > 0 && sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luhn_accepts_known_card() {
+        assert!(luhn_valid("4111111111111111"));
+    }
+
+    #[test]
+    fn test_luhn_rejects_random_id() {
+        assert!(!luhn_valid("1234567812345678"));
+    }
+
+    #[test]
+    fn test_detects_ssn_in_free_text() {
+        let m = detect_pii("the record says 123-45-6789 somewhere");
+        assert!(m.iter().any(|x| x.category == "ssn"));
+    }
+
+    #[test]
+    fn test_detects_card_in_free_text() {
+        let m = detect_pii("charge 4111111111111111 now");
+        assert!(m.iter().any(|x| x.category == "credit_card"));
+    }
+
+    #[test]
+    fn test_ignores_invalid_card_number() {
+        let m = detect_pii("order id 1234567812345678");
+        assert!(!m.iter().any(|x| x.category == "credit_card"));
+    }
+
+    #[test]
+    fn test_detects_email() {
+        let m = detect_pii("contact a.user@example.com");
+        assert!(m.iter().any(|x| x.category == "email"));
+    }
+}
@@ -0,0 +1,174 @@
+// Declarative IAM-style policy document interpreted by `evaluate()`.
+//
+// This turns the hand-edited Rust stub into a data-driven engine: a versioned
+// document of statements, each binding an effect to glob patterns for the
+// resource (`PolicyInput.tool`) and action (`PolicyInput.action`), with an
+// optional equality condition over parameters. Resolution is
+// explicit-deny-overrides-allow, defaulting to deny when nothing matches.
+use std::collections::HashMap;
+
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::glob_match;
+use crate::{PolicyInput, PolicyResult};
+
+/// A versioned policy document.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PolicyDocument {
+    pub version: String,
+    pub statements: Vec<Statement>,
+}
+
+/// The effect of a matching statement.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+/// A single statement. `action` and `resource` accept either a single string
+/// or a list of strings in the source document.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Statement {
+    pub effect: Effect,
+    #[serde(deserialize_with = "deserialize_string_or_slice")]
+    pub action: Vec<String>,
+    #[serde(deserialize_with = "deserialize_string_or_slice")]
+    pub resource: Vec<String>,
+    /// Optional equality condition keyed by parameter name.
+    #[serde(default)]
+    pub condition: Option<HashMap<String, Value>>,
+}
+
+/// Deserialize a field that may be written as a single string or a list of
+/// strings into a `Vec<String>` (mirroring IAM's `Action`/`Resource`).
+fn deserialize_string_or_slice<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrSlice;
+
+    impl<'de> Visitor<'de> for StringOrSlice {
+        type Value = Vec<String>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a string or a list of strings")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(vec![v.to_string()])
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut out = Vec::new();
+            while let Some(item) = seq.next_element::<String>()? {
+                out.push(item);
+            }
+            Ok(out)
+        }
+    }
+
+    deserializer.deserialize_any(StringOrSlice)
+}
+
+impl Statement {
+    fn matches(&self, input: &PolicyInput) -> bool {
+        let action_ok = self.action.iter().any(|p| glob_match(p, &input.action));
+        let resource_ok = self.resource.iter().any(|p| glob_match(p, &input.tool));
+        if !action_ok || !resource_ok {
+            return false;
+        }
+        match &self.condition {
+            None => true,
+            Some(cond) => cond
+                .iter()
+                .all(|(key, expected)| input.parameters.get(key) == Some(expected)),
+        }
+    }
+}
+
+impl PolicyDocument {
+    /// Evaluate the document with explicit-deny-overrides-allow semantics.
+    pub fn evaluate(&self, input: &PolicyInput) -> PolicyResult {
+        let mut allow = false;
+        for statement in &self.statements {
+            if !statement.matches(input) {
+                continue;
+            }
+            match statement.effect {
+                Effect::Deny => return PolicyResult::deny("policy document: matched Deny statement"),
+                Effect::Allow => allow = true,
+            }
+        }
+        if allow {
+            PolicyResult::allow("policy document: matched Allow statement")
+        } else {
+            PolicyResult::deny("policy document: no matching statement (implicit deny)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(tool: &str, action: &str) -> PolicyInput {
+        PolicyInput {
+            tool: tool.into(),
+            action: action.into(),
+            parameters: json!({}),
+            context: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_string_or_slice_deserialization() {
+        let doc: PolicyDocument = serde_json::from_value(json!({
+            "version": "2025-01-01",
+            "statements": [
+                {"effect": "Allow", "action": "read", "resource": ["db_*", "cache_*"]}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(doc.statements[0].action, vec!["read".to_string()]);
+        assert_eq!(doc.statements[0].resource.len(), 2);
+    }
+
+    #[test]
+    fn test_deny_overrides() {
+        let doc = PolicyDocument {
+            version: "v1".into(),
+            statements: vec![
+                Statement {
+                    effect: Effect::Allow,
+                    action: vec!["*".into()],
+                    resource: vec!["*".into()],
+                    condition: None,
+                },
+                Statement {
+                    effect: Effect::Deny,
+                    action: vec!["drop".into()],
+                    resource: vec!["db_*".into()],
+                    condition: None,
+                },
+            ],
+        };
+        assert!(!doc.evaluate(&input("db_users", "drop")).allowed);
+        assert!(doc.evaluate(&input("db_users", "read")).allowed);
+    }
+
+    #[test]
+    fn test_implicit_deny() {
+        let doc = PolicyDocument {
+            version: "v1".into(),
+            statements: vec![],
+        };
+        assert!(!doc.evaluate(&input("x", "y")).allowed);
+    }
+}
@@ -0,0 +1,160 @@
+// Structured multi-rule evaluation output.
+//
+// A single boolean plus one `reason` hides which rule fired. An
+// `EvaluationReport` runs the configured rules, records a per-rule
+// `RuleReport`, and `combine()`s them into a final decision so callers can see
+// exactly which named rule produced the verdict and route approvals per-rule.
+use serde::Serialize;
+
+use crate::{default_scan, PolicyInput, PolicyResult};
+
+/// The outcome of one named rule.
+#[derive(Serialize, Debug, Clone)]
+pub struct RuleReport {
+    pub rule_name: String,
+    pub passed: bool,
+    pub human_required: bool,
+    pub reason: String,
+}
+
+impl RuleReport {
+    fn from_result(rule_name: impl Into<String>, result: &PolicyResult) -> Self {
+        Self {
+            rule_name: rule_name.into(),
+            passed: result.allowed,
+            human_required: result.human_required,
+            reason: result.reason.clone(),
+        }
+    }
+}
+
+/// The full report: a short summary of the request, the per-rule outcomes, and
+/// the combined final decision.
+#[derive(Serialize, Debug, Clone)]
+pub struct EvaluationReport {
+    pub input_summary: String,
+    pub rules: Vec<RuleReport>,
+    pub final_decision: PolicyResult,
+}
+
+/// Derive the final decision from a set of named rule results: deny if any rule
+/// denies, require approval if any rule flags human-required, otherwise allow.
+pub fn combine(rules: &[(String, PolicyResult)]) -> PolicyResult {
+    if let Some((name, r)) = rules
+        .iter()
+        .find(|(_, r)| !r.allowed && !r.human_required)
+    {
+        return PolicyResult::deny(format!("rule `{name}` denied: {}", r.reason));
+    }
+    if let Some((name, r)) = rules.iter().find(|(_, r)| r.human_required) {
+        return PolicyResult::require_approval(
+            format!("rule `{name}` requires approval: {}", r.reason),
+            r.confidence,
+        );
+    }
+    PolicyResult::allow("all rules passed")
+}
+
+/// Build the report for a request by running the `conditions`, `policy` (IAM)
+/// and `model` rules found in `context`, plus the default sensitive-data scan.
+/// The `composite`, `fn_conditions`, `abac` and `document` engines are not
+/// run, and a rule whose entry fails to parse is left out of the report.
+pub fn evaluate_report(input: &PolicyInput) -> EvaluationReport {
+    let mut results: Vec<(String, PolicyResult)> = Vec::new();
+
+    if let Some(raw) = input.context.get("conditions") {
+        if let Ok(conds) = serde_json::from_value::<crate::condition::Conditions>(raw.clone()) {
+            results.push(("conditions".into(), conds.evaluate(input)));
+        }
+    }
+    if let Some(raw) = input.context.get("policy") {
+        if let Ok(doc) = serde_json::from_value::<crate::iam::PolicyDocument>(raw.clone()) {
+            results.push(("iam".into(), doc.evaluate(input)));
+        }
+    }
+    if let Some(raw) = input.context.get("model") {
+        if let Ok(model) = serde_json::from_value::<crate::model::PolicyModel>(raw.clone()) {
+            results.push(("model".into(), model.evaluate(input)));
+        }
+    }
+    // Always run the default sensitive-data scan as a baseline rule.
+    results.push(("sensitive_scan".into(), default_scan(input)));
+
+    let rules = results
+        .iter()
+        .map(|(name, r)| RuleReport::from_result(name, r))
+        .collect();
+    let final_decision = combine(&results);
+
+    EvaluationReport {
+        input_summary: format!("{}.{}", input.tool, input.action),
+        rules,
+        final_decision,
+    }
+}
+
+/// WASM export returning the full `EvaluationReport` JSON.
+///
+/// # Safety
+/// The pointers must describe valid guest memory as set up by the host.
+#[no_mangle]
+pub unsafe extern "C" fn evaluate_report_export(
+    in_ptr: *const u8,
+    in_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let input = std::slice::from_raw_parts(in_ptr, in_len);
+    let bytes = match serde_json::from_slice::<PolicyInput>(input) {
+        Ok(inp) => serde_json::to_vec(&evaluate_report(&inp)).unwrap_or_default(),
+        Err(e) => serde_json::to_vec(&PolicyResult::deny(format!("invalid JSON: {e}")))
+            .unwrap_or_default(),
+    };
+
+    let len = bytes.len();
+    let ptr = crate::alloc(len);
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, len);
+    *out_ptr = ptr;
+    *out_len = len;
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(params: serde_json::Value) -> PolicyInput {
+        PolicyInput {
+            tool: "api".into(),
+            action: "fetch".into(),
+            parameters: params,
+            context: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_combine_deny_wins() {
+        let rules = vec![
+            ("a".into(), PolicyResult::allow("ok")),
+            ("b".into(), PolicyResult::deny("nope")),
+        ];
+        assert!(!combine(&rules).allowed);
+    }
+
+    #[test]
+    fn test_combine_approval() {
+        let rules = vec![
+            ("a".into(), PolicyResult::allow("ok")),
+            ("b".into(), PolicyResult::require_approval("review", 0.9)),
+        ];
+        assert!(combine(&rules).human_required);
+    }
+
+    #[test]
+    fn test_report_flags_pii_rule() {
+        let report = evaluate_report(&input(json!({"ssn": "123-45-6789"})));
+        assert!(report.rules.iter().any(|r| r.rule_name == "sensitive_scan"));
+        assert!(report.final_decision.human_required);
+    }
+}
@@ -0,0 +1,120 @@
+// Tamper-evident signed decisions via an explicit wrapper type.
+//
+// Where `signing` attaches signature fields onto the `PolicyResult` itself,
+// this path returns a `SignedPolicyResult` that pairs the canonical decision
+// with a detached signature over its JSON bytes and the id of the key that
+// produced it. A compromised policy module cannot silently forge allows: the
+// host verifies the signature against the published public key before trusting
+// the decision.
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Serialize;
+
+use crate::signing::{sign_bytes, unhex};
+use crate::{decide, PolicyInput, PolicyResult};
+
+/// A decision together with its detached signature and signing key id.
+#[derive(Serialize, Debug, Clone)]
+pub struct SignedPolicyResult {
+    pub result: PolicyResult,
+    pub signature: String,
+    pub key_id: String,
+}
+
+/// Evaluate a request and wrap the decision in a `SignedPolicyResult`. Returns
+/// `None` when no signing key has been loaded.
+pub fn evaluate_signed(input: &PolicyInput) -> Option<SignedPolicyResult> {
+    let result = decide(input);
+    let bytes = canonical_bytes(&result);
+    let (signature, key_id) = sign_bytes(&bytes)?;
+    Some(SignedPolicyResult { result, signature, key_id })
+}
+
+/// Canonical JSON of the decision fields that are signed. The optional
+/// signature fields on `PolicyResult` are skipped via `skip_serializing_if`, so
+/// the signed bytes cover only the decision itself.
+fn canonical_bytes(result: &PolicyResult) -> Vec<u8> {
+    serde_json::to_vec(result).expect("PolicyResult serializes")
+}
+
+/// Verify a `SignedPolicyResult` against a public key. Provided for round-trip
+/// tests and hosts that embed the verifying key.
+pub fn verify(signed: &SignedPolicyResult, public_key: &VerifyingKey) -> bool {
+    let bytes = canonical_bytes(&signed.result);
+    let sig_bytes = match unhex(&signed.signature) {
+        Some(b) if b.len() == 64 => b,
+        _ => return false,
+    };
+    let mut arr = [0u8; 64];
+    arr.copy_from_slice(&sig_bytes);
+    public_key
+        .verify_strict(&bytes, &Signature::from_bytes(&arr))
+        .is_ok()
+}
+
+/// WASM export: evaluate a request and return the `SignedPolicyResult` JSON.
+/// Falls back to the plain (unsigned) decision when no key is loaded.
+///
+/// # Safety
+/// The pointers must describe valid guest memory as set up by the host.
+#[no_mangle]
+pub unsafe extern "C" fn evaluate_signed_export(
+    in_ptr: *const u8,
+    in_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let input = std::slice::from_raw_parts(in_ptr, in_len);
+    let bytes = match serde_json::from_slice::<PolicyInput>(input) {
+        Ok(inp) => match evaluate_signed(&inp) {
+            Some(signed) => serde_json::to_vec(&signed).unwrap_or_default(),
+            None => serde_json::to_vec(&decide(&inp)).unwrap_or_default(),
+        },
+        Err(e) => serde_json::to_vec(&PolicyResult::deny(format!("invalid JSON: {e}")))
+            .unwrap_or_default(),
+    };
+
+    let len = bytes.len();
+    let ptr = crate::alloc(len);
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, len);
+    *out_ptr = ptr;
+    *out_len = len;
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_round_trip_verifies() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let public = key.verifying_key();
+        let result = PolicyResult::allow("ok");
+        let bytes = canonical_bytes(&result);
+        let sig = key.sign(&bytes);
+        let signed = SignedPolicyResult {
+            result,
+            signature: crate::signing::hex(&sig.to_bytes()),
+            key_id: "test".into(),
+        };
+        assert!(verify(&signed, &public));
+    }
+
+    #[test]
+    fn test_tamper_detected() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let public = key.verifying_key();
+        let result = PolicyResult::allow("ok");
+        let bytes = canonical_bytes(&result);
+        let sig = key.sign(&bytes);
+        let mut signed = SignedPolicyResult {
+            result,
+            signature: crate::signing::hex(&sig.to_bytes()),
+            key_id: "test".into(),
+        };
+        // Flipping the decision invalidates the signature.
+        signed.result.allowed = false;
+        assert!(!verify(&signed, &public));
+    }
+}
@@ -0,0 +1,217 @@
+// Cryptographically signed decisions for a tamper-evident trail.
+//
+// The Go runtime trusts whatever bytes `evaluate` returns, so there is no way
+// to prove a decision came from a particular policy version and was not
+// altered in transit. When a signing key is loaded via `set_signing_key`, each
+// decision can be signed over the canonical JSON of its security-relevant
+// fields plus a monotonically increasing nonce, letting the host verify the
+// decision against the published public key and detect replay or manipulation.
+//
+// Signing is optional: without a loaded key, results serialize exactly as
+// before and remain byte-compatible with unsigned builds.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey, Signature};
+use serde::Serialize;
+
+use crate::{PolicyInput, PolicyResult};
+
+/// The process-wide signing key, loaded once at init.
+static SIGNING_KEY: OnceLock<SigningKey> = OnceLock::new();
+
+/// Monotonic nonce folded into every signed payload to defeat replay.
+static NONCE: AtomicU64 = AtomicU64::new(0);
+
+/// Canonical signing payload. The field order is fixed so that the host can
+/// reconstruct the exact bytes that were signed.
+#[derive(Serialize)]
+struct Payload<'a> {
+    allowed: bool,
+    human_required: bool,
+    reason: &'a str,
+    confidence: f64,
+    tool: &'a str,
+    action: &'a str,
+    nonce: u64,
+}
+
+/// Load the ed25519 secret key (32 raw bytes) used to sign decisions. Returns
+/// `false` if the key material is the wrong length or a key was already set.
+pub fn set_signing_key(secret: &[u8]) -> bool {
+    let bytes: [u8; 32] = match secret.try_into() {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    SIGNING_KEY.set(SigningKey::from_bytes(&bytes)).is_ok()
+}
+
+/// Hex-encoded public key used as the `key_id` on signed results.
+fn key_id(key: &SigningKey) -> String {
+    hex(key.verifying_key().as_bytes())
+}
+
+/// Sign a decision for the given request, populating `signature` and `key_id`.
+/// When no signing key has been loaded the result is returned unchanged so
+/// callers can treat signing as best-effort.
+pub fn sign_result(mut result: PolicyResult, input: &PolicyInput) -> PolicyResult {
+    let Some(key) = SIGNING_KEY.get() else {
+        return result;
+    };
+    let nonce = NONCE.fetch_add(1, Ordering::SeqCst);
+    let payload = Payload {
+        allowed: result.allowed,
+        human_required: result.human_required,
+        reason: &result.reason,
+        confidence: result.confidence,
+        tool: &input.tool,
+        action: &input.action,
+        nonce,
+    };
+    // serde_json emits struct fields in declaration order, giving a canonical
+    // byte string for signing.
+    let bytes = serde_json::to_vec(&payload).expect("payload serializes");
+    let signature = key.sign(&bytes);
+    result.signature = Some(hex(&signature.to_bytes()));
+    result.key_id = Some(key_id(key));
+    result.nonce = Some(nonce);
+    result
+}
+
+/// Verify a signed decision against a public key. Used by round-trip tests and
+/// available to hosts that embed the verifying key.
+pub fn verify(
+    result: &PolicyResult,
+    input: &PolicyInput,
+    public_key: &VerifyingKey,
+) -> bool {
+    let (Some(sig_hex), Some(nonce)) = (&result.signature, result.nonce) else {
+        return false;
+    };
+    let payload = Payload {
+        allowed: result.allowed,
+        human_required: result.human_required,
+        reason: &result.reason,
+        confidence: result.confidence,
+        tool: &input.tool,
+        action: &input.action,
+        nonce,
+    };
+    let bytes = match serde_json::to_vec(&payload) {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    let sig_bytes = match unhex(sig_hex) {
+        Some(b) if b.len() == 64 => b,
+        _ => return false,
+    };
+    let mut arr = [0u8; 64];
+    arr.copy_from_slice(&sig_bytes);
+    let signature = Signature::from_bytes(&arr);
+    public_key.verify_strict(&bytes, &signature).is_ok()
+}
+
+/// Sign an arbitrary message with the loaded key, returning the hex signature
+/// and hex key id. `None` when no key has been loaded. Shared by the wrapper
+/// `SignedPolicyResult` path.
+pub(crate) fn sign_bytes(message: &[u8]) -> Option<(String, String)> {
+    let key = SIGNING_KEY.get()?;
+    let signature = key.sign(message);
+    Some((hex(&signature.to_bytes()), key_id(key)))
+}
+
+/// Lowercase hex-encode bytes. Shared with the `signed` wrapper path.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push_str(&format!("{b:02x}"));
+    }
+    s
+}
+
+/// Decode a lowercase/uppercase hex string, returning `None` on odd length or a
+/// non-hex digit. Shared with the `signed` wrapper path.
+pub(crate) fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// WASM export: load the signing key once at init. The host passes the 32-byte
+/// ed25519 secret through linear memory.
+///
+/// # Safety
+/// `ptr`/`len` must describe a valid readable region of guest memory.
+#[no_mangle]
+pub unsafe extern "C" fn set_signing_key_export(ptr: *const u8, len: usize) -> i32 {
+    let bytes = std::slice::from_raw_parts(ptr, len);
+    if set_signing_key_inner(bytes) {
+        0
+    } else {
+        1
+    }
+}
+
+// Thin wrapper over `set_signing_key`, called by the WASM export.
+fn set_signing_key_inner(bytes: &[u8]) -> bool {
+    set_signing_key(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input() -> PolicyInput {
+        PolicyInput {
+            tool: "db".into(),
+            action: "read".into(),
+            parameters: json!({}),
+            context: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_unsigned_result_byte_compatible() {
+        // With no key loaded the signature fields stay absent from the JSON.
+        let result = PolicyResult::allow("ok");
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("signature"));
+        assert!(!json.contains("key_id"));
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public = key.verifying_key();
+        let input = input();
+
+        // Sign directly with a local key (the global is process-wide).
+        let nonce = 0u64;
+        let payload = Payload {
+            allowed: true,
+            human_required: false,
+            reason: "ok",
+            confidence: 1.0,
+            tool: &input.tool,
+            action: &input.action,
+            nonce,
+        };
+        let bytes = serde_json::to_vec(&payload).unwrap();
+        let signature = key.sign(&bytes);
+
+        let mut result = PolicyResult::allow("ok");
+        result.signature = Some(hex(&signature.to_bytes()));
+        result.key_id = Some(hex(public.as_bytes()));
+        result.nonce = Some(nonce);
+
+        assert!(verify(&result, &input, &public));
+        // A tampered decision fails verification.
+        result.reason = "tampered".into();
+        assert!(!verify(&result, &input, &public));
+    }
+}